@@ -10,3 +10,19 @@ cfg_alloc! {
         axalloc::global_allocator().dealloc(ptr, layout)
     }
 }
+
+#[cfg(feature = "alloc-tracking")]
+pub fn ax_dump_allocations() {
+    struct Console;
+
+    impl core::fmt::Write for Console {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            axhal::console::write_bytes(s.as_bytes());
+            Ok(())
+        }
+    }
+
+    axalloc::global_allocator()
+        .dump_allocations(&mut Console)
+        .unwrap()
+}
@@ -64,6 +64,13 @@ pub mod mem {
         /// `layout`, which should be allocated by [`ax_alloc`].
         pub fn ax_dealloc(ptr: NonNull<u8>, layout: Layout);
     }
+
+    define_api! {
+        @cfg "alloc-tracking";
+        /// Prints all live allocations in the global allocator to the console,
+        /// together with the return addresses of their allocation sites.
+        pub fn ax_dump_allocations();
+    }
 }
 
 /// Standard input and output.
@@ -108,6 +115,8 @@ pub mod task {
 
         /// Returns the current task's ID.
         pub fn ax_current_task_id() -> u64;
+        /// Returns the current task's name.
+        pub fn ax_current_task_name() -> alloc::string::String;
         /// Spawns a new task with the given entry point and other arguments.
         pub fn ax_spawn(
             f: impl FnOnce() + Send + 'static,
@@ -6,7 +6,7 @@
 extern crate axstd as std;
 
 use rand::{rngs::SmallRng, RngCore, SeedableRng};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::vec::Vec;
 
 fn test_vec(rng: &mut impl RngCore) {
@@ -38,6 +38,48 @@ fn test_btree_map(rng: &mut impl RngCore) {
     println!("test_btree_map() OK!");
 }
 
+fn test_hash_map(rng: &mut impl RngCore) {
+    const N: usize = 50_000;
+    let mut m = HashMap::new();
+    for _ in 0..N {
+        let value = rng.next_u32();
+        let key = format!("key_{value}");
+        m.insert(key, value);
+    }
+    for (k, v) in m.iter() {
+        if let Some(k) = k.strip_prefix("key_") {
+            assert_eq!(k.parse::<u32>().unwrap(), *v);
+        }
+    }
+    let mut count = HashMap::new();
+    for v in m.values() {
+        *count.entry(v % 16).or_insert(0) += 1;
+    }
+    assert_eq!(count.values().sum::<usize>(), m.len());
+    m.retain(|_, v| *v % 2 == 0);
+    assert!(m.values().all(|v| *v % 2 == 0));
+    println!("test_hash_map() OK!");
+}
+
+fn test_hash_set(rng: &mut impl RngCore) {
+    const N: usize = 50_000;
+    let mut v = Vec::with_capacity(N);
+    let mut s = HashSet::new();
+    for _ in 0..N {
+        let value = rng.next_u32() % (N as u32);
+        v.push(value);
+        s.insert(value);
+    }
+    for value in &v {
+        assert!(s.contains(value));
+    }
+    for value in &v {
+        s.remove(value);
+    }
+    assert!(s.is_empty());
+    println!("test_hash_set() OK!");
+}
+
 #[cfg_attr(feature = "axstd", no_mangle)]
 fn main() {
     println!("Running memory tests...");
@@ -45,6 +87,8 @@ fn main() {
     let mut rng = SmallRng::seed_from_u64(0xdead_beef);
     test_vec(&mut rng);
     test_btree_map(&mut rng);
+    test_hash_map(&mut rng);
+    test_hash_set(&mut rng);
 
     println!("Memory tests run OK!");
 }
@@ -0,0 +1,258 @@
+#![cfg_attr(feature = "axstd", no_std)]
+#![cfg_attr(feature = "axstd", no_main)]
+
+#[macro_use]
+#[cfg(feature = "axstd")]
+extern crate axstd as std;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, SendError, TryRecvError, TrySendError};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::thread;
+use std::time::Duration;
+use std::vec::Vec;
+
+const NUM_TASKS: usize = 8;
+const NUM_ITERS: usize = 1000;
+
+// `std` returns a `LockResult` from the locking functions, while `axstd` has no
+// lock poisoning.
+#[cfg(feature = "axstd")]
+mod compat {
+    use super::*;
+
+    pub fn lock<T>(m: &Mutex<T>) -> MutexGuard<'_, T> {
+        m.lock()
+    }
+    pub fn read<T>(l: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+        l.read()
+    }
+    pub fn write<T>(l: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+        l.write()
+    }
+    pub fn can_write<T>(l: &RwLock<T>) -> bool {
+        l.try_write().is_some()
+    }
+    pub fn wait_while<'a, T>(
+        cv: &Condvar,
+        guard: MutexGuard<'a, T>,
+        condition: impl FnMut(&mut T) -> bool,
+    ) -> MutexGuard<'a, T> {
+        cv.wait_while(guard, condition)
+    }
+}
+
+#[cfg(not(feature = "axstd"))]
+mod compat {
+    use super::*;
+
+    pub fn lock<T>(m: &Mutex<T>) -> MutexGuard<'_, T> {
+        m.lock().unwrap()
+    }
+    pub fn read<T>(l: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+        l.read().unwrap()
+    }
+    pub fn write<T>(l: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+        l.write().unwrap()
+    }
+    pub fn can_write<T>(l: &RwLock<T>) -> bool {
+        l.try_write().is_ok()
+    }
+    pub fn wait_while<'a, T>(
+        cv: &Condvar,
+        guard: MutexGuard<'a, T>,
+        condition: impl FnMut(&mut T) -> bool,
+    ) -> MutexGuard<'a, T> {
+        cv.wait_while(guard, condition).unwrap()
+    }
+}
+
+use compat::*;
+
+fn test_condvar() {
+    // All tasks wait for the start signal.
+    let pair = Arc::new((Mutex::new(false), Condvar::new()));
+    let started = Arc::new(AtomicUsize::new(0));
+    let tasks: Vec<_> = (0..NUM_TASKS)
+        .map(|_| {
+            let pair = pair.clone();
+            let started = started.clone();
+            thread::spawn(move || {
+                let (mutex, cvar) = &*pair;
+                let guard = wait_while(cvar, lock(mutex), |ready| !*ready);
+                assert!(*guard);
+                started.fetch_add(1, Ordering::Relaxed);
+            })
+        })
+        .collect();
+    thread::sleep(Duration::from_millis(10));
+    assert_eq!(started.load(Ordering::Relaxed), 0);
+    *lock(&pair.0) = true;
+    pair.1.notify_all();
+    for t in tasks {
+        t.join().unwrap();
+    }
+    assert_eq!(started.load(Ordering::Relaxed), NUM_TASKS);
+
+    // Ping-pong between two tasks with `notify_one`.
+    let pair = Arc::new((Mutex::new(0usize), Condvar::new()));
+    let pair2 = pair.clone();
+    let odd = thread::spawn(move || {
+        let (mutex, cvar) = &*pair2;
+        for i in 0..NUM_ITERS {
+            let mut n = wait_while(cvar, lock(mutex), |n| *n % 2 == 0);
+            assert_eq!(*n, i * 2 + 1);
+            *n += 1;
+            cvar.notify_one();
+        }
+    });
+    let (mutex, cvar) = &*pair;
+    for i in 0..NUM_ITERS {
+        let mut n = wait_while(cvar, lock(mutex), |n| *n % 2 == 1);
+        assert_eq!(*n, i * 2);
+        *n += 1;
+        cvar.notify_one();
+    }
+    odd.join().unwrap();
+    assert_eq!(*lock(mutex), NUM_ITERS * 2);
+
+    println!("Condvar tests OK!");
+}
+
+fn test_rwlock() {
+    let data = Arc::new(RwLock::new((0usize, 0usize)));
+    let writer = {
+        let data = data.clone();
+        thread::spawn(move || {
+            for _ in 0..NUM_ITERS {
+                let mut guard = write(&data);
+                guard.0 += 1;
+                thread::yield_now();
+                guard.1 += 1;
+            }
+        })
+    };
+    let readers: Vec<_> = (0..NUM_TASKS - 1)
+        .map(|_| {
+            let data = data.clone();
+            thread::spawn(move || {
+                let mut last = 0;
+                while last < NUM_ITERS {
+                    let guard = read(&data);
+                    assert_eq!(guard.0, guard.1);
+                    assert!(guard.0 >= last);
+                    last = guard.0;
+                    drop(guard);
+                    thread::yield_now();
+                }
+            })
+        })
+        .collect();
+    writer.join().unwrap();
+    for t in readers {
+        t.join().unwrap();
+    }
+
+    let guard = read(&data);
+    let guard2 = read(&data);
+    assert_eq!(*guard, (NUM_ITERS, NUM_ITERS));
+    assert!(!can_write(&data));
+    drop((guard, guard2));
+    assert!(can_write(&data));
+
+    println!("RwLock tests OK!");
+}
+
+fn test_mpsc() {
+    // Unbounded, multiple producers.
+    let (tx, rx) = mpsc::channel();
+    let tasks: Vec<_> = (0..NUM_TASKS)
+        .map(|i| {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for j in 0..NUM_ITERS {
+                    tx.send(i * NUM_ITERS + j).unwrap();
+                }
+            })
+        })
+        .collect();
+    drop(tx);
+    let mut received: Vec<usize> = rx.iter().collect();
+    for t in tasks {
+        t.join().unwrap();
+    }
+    received.sort();
+    assert!(received.into_iter().eq(0..NUM_TASKS * NUM_ITERS));
+
+    // Bounded and rendezvous channels keep the order.
+    for bound in [0, 1, 4] {
+        let (tx, rx) = mpsc::sync_channel(bound);
+        let producer = thread::spawn(move || {
+            for i in 0..NUM_ITERS {
+                tx.send(i).unwrap();
+            }
+        });
+        assert!(rx.iter().eq(0..NUM_ITERS));
+        producer.join().unwrap();
+    }
+
+    // Bounded channels refuse to go beyond their capacity.
+    let (tx, rx) = mpsc::sync_channel(1);
+    tx.try_send(1).unwrap();
+    assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+    assert_eq!(rx.recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+    // Rendezvous `try_send` only succeeds with a waiting receiver.
+    let (tx, rx) = mpsc::sync_channel(0);
+    assert_eq!(tx.try_send(1), Err(TrySendError::Full(1)));
+    let consumer = thread::spawn(move || rx.recv().unwrap());
+    while let Err(TrySendError::Full(_)) = tx.try_send(2) {
+        thread::yield_now();
+    }
+    assert_eq!(consumer.join().unwrap(), 2);
+
+    // A rendezvous send returns its message if the receiver hangs up.
+    let (tx, rx) = mpsc::sync_channel(0);
+    let producer = thread::spawn(move || tx.send(3));
+    thread::sleep(Duration::from_millis(10));
+    drop(rx);
+    assert_eq!(producer.join().unwrap(), Err(SendError(3)));
+
+    // Timeout and disconnection on the receiving side.
+    let (tx, rx) = mpsc::channel::<usize>();
+    assert_eq!(
+        rx.recv_timeout(Duration::from_millis(10)),
+        Err(RecvTimeoutError::Timeout)
+    );
+    drop(tx);
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    assert!(rx.recv().is_err());
+
+    println!("mpsc tests OK!");
+}
+
+#[cfg(feature = "axstd")]
+fn test_exit_code() {
+    let t = thread::spawn(|| -> usize { thread::exit(3) });
+    assert_eq!(t.join_with_exit_code(), (None, 3));
+    let t = thread::spawn(|| 42);
+    assert_eq!(t.join_with_exit_code(), (Some(42), 0));
+    let t = thread::spawn(|| -> usize { thread::exit(1) });
+    assert!(t.join().is_err());
+
+    println!("Thread exit code tests OK!");
+}
+
+#[cfg_attr(feature = "axstd", no_mangle)]
+fn main() {
+    println!("Running sync primitives tests...");
+
+    test_condvar();
+    test_rwlock();
+    test_mpsc();
+    #[cfg(feature = "axstd")]
+    test_exit_code();
+
+    println!("Sync primitives tests run OK!");
+}
@@ -78,7 +78,10 @@ impl<const PAGE_SIZE: usize> PageAllocator for BitmapPageAllocator<PAGE_SIZE> {
     fn dealloc_pages(&mut self, pos: usize, num_pages: usize) {
         // TODO: not decrease `used_pages` if deallocation failed
         self.used_pages -= num_pages;
-        self.inner.dealloc((pos - self.base) / PAGE_SIZE)
+        let start_idx = (pos - self.base) / PAGE_SIZE;
+        for idx in start_idx..start_idx + num_pages {
+            self.inner.dealloc(idx)
+        }
     }
 
     fn total_pages(&self) -> usize {
@@ -7,6 +7,9 @@
 //! - [`PageAllocator`]: Page-granularity memory allocator. (e.g.,
 //!   [`BitmapPageAllocator`])
 //! - [`IdAllocator`]: Used to allocate unique IDs.
+//!
+//! With the `tracking` feature, any [`ByteAllocator`] can be wrapped in a
+//! `TrackingByteAllocator` to record live allocations for leak debugging.
 
 #![no_std]
 #![feature(result_option_inspect)]
@@ -32,6 +35,11 @@ mod tlsf;
 #[cfg(feature = "tlsf")]
 pub use tlsf::TlsfByteAllocator;
 
+#[cfg(feature = "tracking")]
+mod tracking;
+#[cfg(feature = "tracking")]
+pub use tracking::{AllocRecord, TrackingByteAllocator};
+
 use core::alloc::Layout;
 use core::ptr::NonNull;
 
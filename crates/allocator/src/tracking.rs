@@ -0,0 +1,165 @@
+//! Allocation tracking for debugging memory leaks.
+
+use core::alloc::Layout;
+use core::fmt;
+use core::ptr::NonNull;
+
+use crate::{AllocResult, BaseAllocator, ByteAllocator};
+
+/// A live allocation recorded by [`TrackingByteAllocator`].
+#[derive(Debug, Clone, Copy)]
+pub struct AllocRecord {
+    /// Start address of the allocated memory block.
+    pub addr: usize,
+    /// Layout of the allocation.
+    pub layout: Layout,
+    /// Sequence number of the allocation, counted from 0 since the allocator
+    /// was created.
+    pub seq: u64,
+    /// Address of the code that requested the allocation (see
+    /// [`TrackingByteAllocator::alloc_from`]), or 0 if unknown.
+    pub caller: usize,
+}
+
+/// A wrapper of any [`ByteAllocator`] that records live allocations.
+///
+/// Up to `N` live allocations are recorded in a fixed-size table, which can be
+/// inspected with [`live_allocations`] or printed with [`dump`]. Allocations
+/// made when the table is full are served normally but only counted (see
+/// [`untracked`]).
+///
+/// The [`ByteAllocator`] interface does not know who is allocating, so callers
+/// that can find out (e.g. from the return address) should use [`alloc_from`]
+/// instead of [`ByteAllocator::alloc`].
+///
+/// [`alloc_from`]: TrackingByteAllocator::alloc_from
+/// [`live_allocations`]: TrackingByteAllocator::live_allocations
+/// [`dump`]: TrackingByteAllocator::dump
+/// [`untracked`]: TrackingByteAllocator::untracked
+pub struct TrackingByteAllocator<A, const N: usize> {
+    inner: A,
+    records: [Option<AllocRecord>; N],
+    next_seq: u64,
+    untracked: usize,
+}
+
+impl<A: ByteAllocator, const N: usize> TrackingByteAllocator<A, N> {
+    /// Creates a new `TrackingByteAllocator` wrapping `inner`.
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            records: [None; N],
+            next_seq: 0,
+            untracked: 0,
+        }
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Allocates memory with the given layout like [`ByteAllocator::alloc`],
+    /// and records `caller` as the address of the allocation site.
+    pub fn alloc_from(&mut self, layout: Layout, caller: usize) -> AllocResult<NonNull<u8>> {
+        let ptr = self.inner.alloc(layout)?;
+        self.record(ptr.as_ptr() as usize, layout, caller);
+        Ok(ptr)
+    }
+
+    /// Returns an iterator over the recorded live allocations.
+    pub fn live_allocations(&self) -> impl Iterator<Item = &AllocRecord> {
+        self.records.iter().flatten()
+    }
+
+    /// Returns the number of live allocations that were not recorded because
+    /// the table was full.
+    pub fn untracked(&self) -> usize {
+        self.untracked
+    }
+
+    /// Writes a report of all recorded live allocations to `w`.
+    pub fn dump(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        let mut count = 0;
+        let mut bytes = 0;
+        for r in self.live_allocations() {
+            writeln!(
+                w,
+                "#{}: [{:#x}, {:#x}) size={} align={} caller={:#x}",
+                r.seq,
+                r.addr,
+                r.addr + r.layout.size(),
+                r.layout.size(),
+                r.layout.align(),
+                r.caller,
+            )?;
+            count += 1;
+            bytes += r.layout.size();
+        }
+        writeln!(
+            w,
+            "{} live allocations ({} bytes) recorded, {} untracked",
+            count, bytes, self.untracked
+        )
+    }
+
+    fn record(&mut self, addr: usize, layout: Layout, caller: usize) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        match self.records.iter_mut().find(|r| r.is_none()) {
+            Some(slot) => {
+                *slot = Some(AllocRecord {
+                    addr,
+                    layout,
+                    seq,
+                    caller,
+                })
+            }
+            None => self.untracked += 1,
+        }
+    }
+
+    fn unrecord(&mut self, addr: usize) {
+        match self
+            .records
+            .iter_mut()
+            .find(|r| r.is_some_and(|r| r.addr == addr))
+        {
+            Some(slot) => *slot = None,
+            None => self.untracked = self.untracked.saturating_sub(1),
+        }
+    }
+}
+
+impl<A: ByteAllocator, const N: usize> BaseAllocator for TrackingByteAllocator<A, N> {
+    fn init(&mut self, start: usize, size: usize) {
+        self.inner.init(start, size)
+    }
+
+    fn add_memory(&mut self, start: usize, size: usize) -> AllocResult {
+        self.inner.add_memory(start, size)
+    }
+}
+
+impl<A: ByteAllocator, const N: usize> ByteAllocator for TrackingByteAllocator<A, N> {
+    fn alloc(&mut self, layout: Layout) -> AllocResult<NonNull<u8>> {
+        self.alloc_from(layout, 0)
+    }
+
+    fn dealloc(&mut self, pos: NonNull<u8>, layout: Layout) {
+        self.unrecord(pos.as_ptr() as usize);
+        self.inner.dealloc(pos, layout)
+    }
+
+    fn total_bytes(&self) -> usize {
+        self.inner.total_bytes()
+    }
+
+    fn used_bytes(&self) -> usize {
+        self.inner.used_bytes()
+    }
+
+    fn available_bytes(&self) -> usize {
+        self.inner.available_bytes()
+    }
+}
@@ -5,7 +5,10 @@ use std::alloc::{Allocator, Layout};
 use std::collections::BTreeMap;
 use std::io::Write;
 
-use allocator::{AllocatorRc, BuddyByteAllocator, SlabByteAllocator, TlsfByteAllocator};
+use allocator::{
+    AllocatorRc, BaseAllocator, BitmapPageAllocator, BuddyByteAllocator, PageAllocator,
+    SlabByteAllocator, TlsfByteAllocator, TrackingByteAllocator,
+};
 use rand::{prelude::SliceRandom, Rng};
 
 const POOL_SIZE: usize = 1024 * 1024 * 128;
@@ -141,3 +144,75 @@ fn tlsf_alloc() {
         test_btree_map(50_000, &alloc);
     })
 }
+
+#[test]
+fn tracking_alloc() {
+    run_test(|pool| {
+        let alloc = AllocatorRc::new(
+            TrackingByteAllocator::<_, 4096>::new(TlsfByteAllocator::new()),
+            pool,
+        );
+        test_alignment(50, &alloc);
+        test_vec(3_000_000, &alloc);
+        test_vec2(30_000, 64, &alloc);
+        test_btree_map(50_000, &alloc);
+    });
+    run_test(|pool| {
+        use allocator::ByteAllocator;
+
+        let mut alloc = TrackingByteAllocator::<_, 2>::new(TlsfByteAllocator::new());
+        alloc.init(pool.as_mut_ptr() as usize, pool.len());
+        let layouts = [
+            Layout::from_size_align(16, 8).unwrap(),
+            Layout::from_size_align(4096, 4096).unwrap(),
+            Layout::from_size_align(100, 4).unwrap(),
+        ];
+        let ptrs = [
+            alloc.alloc(layouts[0]).unwrap(),
+            alloc.alloc_from(layouts[1], 0x1234).unwrap(),
+            alloc.alloc(layouts[2]).unwrap(),
+        ];
+        assert_eq!(alloc.live_allocations().count(), 2);
+        assert_eq!(alloc.untracked(), 1);
+
+        alloc.dealloc(ptrs[0], layouts[0]);
+        let live: Vec<_> = alloc.live_allocations().collect();
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].addr, ptrs[1].as_ptr() as usize);
+        assert_eq!(live[0].layout, layouts[1]);
+        assert_eq!(live[0].seq, 1);
+        assert_eq!(live[0].caller, 0x1234);
+
+        let mut report = String::new();
+        alloc.dump(&mut report).unwrap();
+        assert!(report.contains("#1: ") && report.contains("caller=0x1234"));
+        assert!(report.ends_with("1 live allocations (4096 bytes) recorded, 1 untracked\n"));
+
+        alloc.dealloc(ptrs[1], layouts[1]);
+        alloc.dealloc(ptrs[2], layouts[2]);
+        assert_eq!(alloc.live_allocations().count(), 0);
+        assert_eq!(alloc.untracked(), 0);
+    })
+}
+
+#[test]
+fn bitmap_page_alloc() {
+    const PAGE_SIZE: usize = 4096;
+    let mut alloc = BitmapPageAllocator::<PAGE_SIZE>::new();
+    alloc.init(0x8000_0000, 64 * PAGE_SIZE);
+    assert_eq!(alloc.total_pages(), 64);
+
+    let a = alloc.alloc_pages(16, PAGE_SIZE).unwrap();
+    let b = alloc.alloc_pages(16, 16 * PAGE_SIZE).unwrap();
+    assert_eq!(b % (16 * PAGE_SIZE), 0);
+    assert_eq!(alloc.used_pages(), 32);
+
+    // all pages of a contiguous block must be freed, not only the first one
+    alloc.dealloc_pages(a, 16);
+    alloc.dealloc_pages(b, 16);
+    assert_eq!(alloc.used_pages(), 0);
+    let c = alloc.alloc_pages(64, PAGE_SIZE).unwrap();
+    assert_eq!(c, 0x8000_0000);
+    alloc.dealloc_pages(c, 64);
+    assert_eq!(alloc.available_pages(), 64);
+}
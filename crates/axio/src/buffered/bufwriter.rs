@@ -0,0 +1,127 @@
+use crate::{Result, Seek, SeekFrom, Write};
+use axerrno::ax_err;
+use core::mem::ManuallyDrop;
+
+const DEFAULT_BUF_SIZE: usize = 1024;
+
+/// Wraps a writer and buffers its output.
+///
+/// It collects small writes in an internal buffer and forwards them to the
+/// underlying writer in large, infrequent batches. The buffer is written out
+/// when the `BufWriter<W>` is dropped, but any errors that happen then are
+/// ignored, so call [`flush`] explicitly to observe them.
+///
+/// [`flush`]: BufWriter::flush
+pub struct BufWriter<W: Write> {
+    inner: W,
+    len: usize,
+    buf: [u8; DEFAULT_BUF_SIZE],
+}
+
+impl<W: Write> BufWriter<W> {
+    /// Creates a new `BufWriter<W>` with a default buffer capacity (1 KB).
+    pub const fn new(inner: W) -> BufWriter<W> {
+        Self {
+            inner,
+            len: 0,
+            buf: [0; DEFAULT_BUF_SIZE],
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the internally buffered data.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Returns the number of bytes the internal buffer can hold without
+    /// flushing.
+    pub const fn capacity(&self) -> usize {
+        DEFAULT_BUF_SIZE
+    }
+
+    /// Unwraps this `BufWriter<W>`, returning the underlying writer.
+    ///
+    /// The buffer is written out before returning the writer. If that fails,
+    /// the error is returned and the `BufWriter<W>` is dropped.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.flush_buf()?;
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` will never be dropped, so `inner` is moved out only
+        // once.
+        Ok(unsafe { core::ptr::read(&this.inner) })
+    }
+
+    const fn spare_capacity(&self) -> usize {
+        DEFAULT_BUF_SIZE - self.len
+    }
+
+    /// Sends the whole buffer to the underlying writer.
+    fn flush_buf(&mut self) -> Result {
+        let mut written = 0;
+        let ret = loop {
+            if written >= self.len {
+                break Ok(());
+            }
+            match self.inner.write(&self.buf[written..self.len]) {
+                Ok(0) => break ax_err!(WriteZero, "failed to write the buffered data"),
+                Ok(n) => written += n,
+                Err(e) => break Err(e),
+            }
+        };
+        // Keep the unwritten data for the next attempt.
+        self.buf.copy_within(written..self.len, 0);
+        self.len -= written;
+        ret
+    }
+}
+
+impl<W: Write> Write for BufWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if buf.len() > self.spare_capacity() {
+            self.flush_buf()?;
+        }
+        // If the data is larger than our internal buffer, bypass the buffer
+        // entirely.
+        if buf.len() >= self.capacity() {
+            self.inner.write(buf)
+        } else {
+            self.buf[self.len..self.len + buf.len()].copy_from_slice(buf);
+            self.len += buf.len();
+            Ok(buf.len())
+        }
+    }
+
+    fn flush(&mut self) -> Result {
+        self.flush_buf()?;
+        self.inner.flush()
+    }
+}
+
+impl<W: Write + Seek> Seek for BufWriter<W> {
+    /// Seek to the offset, in bytes, in the underlying writer.
+    ///
+    /// Seeking always writes out the internal buffer before seeking.
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.flush_buf()?;
+        self.inner.seek(pos)
+    }
+}
+
+impl<W: Write> Drop for BufWriter<W> {
+    fn drop(&mut self) {
+        // dtors should not panic, so we ignore a failed flush
+        let _r = self.flush_buf();
+    }
+}
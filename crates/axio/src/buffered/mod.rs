@@ -1,3 +1,5 @@
 mod bufreader;
+mod bufwriter;
 
 pub use self::bufreader::BufReader;
+pub use self::bufwriter::BufWriter;
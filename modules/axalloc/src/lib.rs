@@ -4,8 +4,16 @@
 //! [`core::alloc::GlobalAlloc`]. A static global variable of type
 //! [`GlobalAllocator`] is defined with the `#[global_allocator]` attribute, to
 //! be registered as the standard library’s default allocator.
+//!
+//! # Cargo Features
+//!
+//! - `tlsf`, `slab`, `buddy`: Select the byte allocator (defaults to `tlsf`).
+//! - `tracking`: Record live allocations, together with the return addresses
+//!   of their allocation sites, so that they can be reported with
+//!   [`GlobalAllocator::dump_allocations`] to debug memory leaks.
 
 #![no_std]
+#![cfg_attr(feature = "tracking", feature(return_address))]
 
 #[macro_use]
 extern crate log;
@@ -25,14 +33,38 @@ pub use page::GlobalPage;
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "slab")] {
-        use allocator::SlabByteAllocator as DefaultByteAllocator;
+        use allocator::SlabByteAllocator as SelectedByteAllocator;
     } else if #[cfg(feature = "buddy")] {
-        use allocator::BuddyByteAllocator as DefaultByteAllocator;
+        use allocator::BuddyByteAllocator as SelectedByteAllocator;
     } else if #[cfg(feature = "tlsf")] {
-        use allocator::TlsfByteAllocator as DefaultByteAllocator;
+        use allocator::TlsfByteAllocator as SelectedByteAllocator;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "tracking")] {
+        /// Maximum number of live allocations recorded with the `tracking`
+        /// feature.
+        const MAX_TRACKED_ALLOCS: usize = 4096;
+        type DefaultByteAllocator =
+            allocator::TrackingByteAllocator<SelectedByteAllocator, MAX_TRACKED_ALLOCS>;
+    } else {
+        type DefaultByteAllocator = SelectedByteAllocator;
     }
 }
 
+/// Returns the return address of the function it is expanded in, or 0 if the
+/// `tracking` feature is disabled.
+macro_rules! caller_addr {
+    () => {{
+        #[cfg(feature = "tracking")]
+        let addr = core::arch::return_address!() as usize;
+        #[cfg(not(feature = "tracking"))]
+        let addr = 0;
+        addr
+    }};
+}
+
 /// The global allocator used by ArceOS.
 ///
 /// It combines a [`ByteAllocator`] and a [`PageAllocator`] into a simple
@@ -53,11 +85,21 @@ impl GlobalAllocator {
     /// Creates an empty [`GlobalAllocator`].
     pub const fn new() -> Self {
         Self {
-            balloc: SpinNoIrq::new(DefaultByteAllocator::new()),
+            balloc: SpinNoIrq::new(Self::new_byte_allocator()),
             palloc: SpinNoIrq::new(BitmapPageAllocator::new()),
         }
     }
 
+    const fn new_byte_allocator() -> DefaultByteAllocator {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "tracking")] {
+                DefaultByteAllocator::new(SelectedByteAllocator::new())
+            } else {
+                DefaultByteAllocator::new()
+            }
+        }
+    }
+
     /// Returns the name of the allocator.
     pub const fn name(&self) -> &'static str {
         cfg_if::cfg_if! {
@@ -103,10 +145,20 @@ impl GlobalAllocator {
     /// `align_pow2` must be a power of 2, and the returned region bound will be
     ///  aligned to it.
     pub fn alloc(&self, layout: Layout) -> AllocResult<NonNull<u8>> {
+        self.alloc_from(layout, caller_addr!())
+    }
+
+    /// Same as [`alloc`](GlobalAllocator::alloc), but records `caller` as the
+    /// allocation site if the `tracking` feature is enabled.
+    fn alloc_from(&self, layout: Layout, _caller: usize) -> AllocResult<NonNull<u8>> {
         // simple two-level allocator: if no heap memory, allocate from the page allocator.
         let mut balloc = self.balloc.lock();
         loop {
-            if let Ok(ptr) = balloc.alloc(layout) {
+            #[cfg(feature = "tracking")]
+            let res = balloc.alloc_from(layout, _caller);
+            #[cfg(not(feature = "tracking"))]
+            let res = balloc.alloc(layout);
+            if let Ok(ptr) = res {
                 return Ok(ptr);
             } else {
                 let old_size = balloc.total_bytes();
@@ -176,11 +228,24 @@ impl GlobalAllocator {
     pub fn available_pages(&self) -> usize {
         self.palloc.lock().available_pages()
     }
+
+    /// Writes a report of the live allocations in the byte allocator to `w`.
+    ///
+    /// The byte allocator is locked during the whole report, so `w` must not
+    /// allocate memory (e.g., writing to the console is fine, but writing to a
+    /// `String` will deadlock).
+    #[cfg(feature = "tracking")]
+    pub fn dump_allocations(&self, w: &mut impl core::fmt::Write) -> core::fmt::Result {
+        self.balloc.lock().dump(w)
+    }
 }
 
 unsafe impl GlobalAlloc for GlobalAllocator {
+    // Inlined into the allocator shim (`__rust_alloc`), so that the return
+    // address is the real allocation site.
+    #[cfg_attr(feature = "tracking", inline(always))]
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        if let Ok(ptr) = GlobalAllocator::alloc(self, layout) {
+        if let Ok(ptr) = GlobalAllocator::alloc_from(self, layout, caller_addr!()) {
             ptr.as_ptr()
         } else {
             alloc::alloc::handle_alloc_error(layout)
@@ -114,6 +114,7 @@ if [ -z "$1" ]; then
         "apps/task/sleep"
         "apps/task/priority"
         "apps/task/tls"
+        "apps/task/sync"
         "apps/net/httpclient"
         "apps/c/helloworld"
         "apps/c/memtest"
@@ -0,0 +1,353 @@
+//! A hash set implemented as a [`HashMap`] where the value is `()`.
+//!
+//! [`HashMap`]: super::HashMap
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{BuildHasher, Hash};
+
+use super::hash_map::RandomState;
+
+#[doc(no_inline)]
+pub use hashbrown::hash_set::{
+    Difference, Drain, Intersection, IntoIter, Iter, SymmetricDifference, Union,
+};
+
+/// A hash set, similar to [`std::collections::HashSet`].
+///
+/// It is a thin wrapper of [`hashbrown::HashSet`], using [`RandomState`] as
+/// the default hasher.
+///
+/// [`std::collections::HashSet`]: https://doc.rust-lang.org/std/collections/struct.HashSet.html
+pub struct HashSet<T, S = RandomState> {
+    base: hashbrown::HashSet<T, S>,
+}
+
+impl<T> HashSet<T, RandomState> {
+    /// Creates an empty `HashSet`.
+    ///
+    /// The hash set is initially created with a capacity of 0, so it will not
+    /// allocate until it is first inserted into.
+    #[inline]
+    pub fn new() -> HashSet<T, RandomState> {
+        Default::default()
+    }
+
+    /// Creates an empty `HashSet` with at least the specified capacity.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> HashSet<T, RandomState> {
+        HashSet::with_capacity_and_hasher(capacity, Default::default())
+    }
+}
+
+impl<T, S> HashSet<T, S> {
+    /// Creates a new empty hash set which will use the given hasher to hash
+    /// keys.
+    #[inline]
+    pub const fn with_hasher(hasher: S) -> HashSet<T, S> {
+        HashSet {
+            base: hashbrown::HashSet::with_hasher(hasher),
+        }
+    }
+
+    /// Creates an empty `HashSet` with at least the specified capacity, using
+    /// `hasher` to hash the keys.
+    #[inline]
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> HashSet<T, S> {
+        HashSet {
+            base: hashbrown::HashSet::with_capacity_and_hasher(capacity, hasher),
+        }
+    }
+
+    /// Returns the number of elements the set can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.base.capacity()
+    }
+
+    /// An iterator visiting all elements in arbitrary order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.base.iter()
+    }
+
+    /// Returns the number of elements in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.base.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.base.is_empty()
+    }
+
+    /// Clears the set, returning all elements as an iterator. Keeps the
+    /// allocated memory for reuse.
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        self.base.drain()
+    }
+
+    /// Retains only the elements specified by the predicate.
+    #[inline]
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.base.retain(f)
+    }
+
+    /// Clears the set, removing all values.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.base.clear()
+    }
+
+    /// Returns a reference to the set's [`BuildHasher`].
+    #[inline]
+    pub fn hasher(&self) -> &S {
+        self.base.hasher()
+    }
+}
+
+impl<T, S> HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Reserves capacity for at least `additional` more elements to be
+    /// inserted in the `HashSet`.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.base.reserve(additional)
+    }
+
+    /// Shrinks the capacity of the set as much as possible.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.base.shrink_to_fit()
+    }
+
+    /// Visits the values representing the difference, i.e., the values that
+    /// are in `self` but not in `other`.
+    #[inline]
+    pub fn difference<'a>(&'a self, other: &'a HashSet<T, S>) -> Difference<'a, T, S> {
+        self.base.difference(&other.base)
+    }
+
+    /// Visits the values representing the symmetric difference, i.e., the
+    /// values that are in `self` or in `other` but not in both.
+    #[inline]
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a HashSet<T, S>,
+    ) -> SymmetricDifference<'a, T, S> {
+        self.base.symmetric_difference(&other.base)
+    }
+
+    /// Visits the values representing the intersection, i.e., the values that
+    /// are both in `self` and `other`.
+    #[inline]
+    pub fn intersection<'a>(&'a self, other: &'a HashSet<T, S>) -> Intersection<'a, T, S> {
+        self.base.intersection(&other.base)
+    }
+
+    /// Visits the values representing the union, i.e., all the values in
+    /// `self` or `other`, without duplicates.
+    #[inline]
+    pub fn union<'a>(&'a self, other: &'a HashSet<T, S>) -> Union<'a, T, S> {
+        self.base.union(&other.base)
+    }
+
+    /// Returns `true` if the set contains a value.
+    #[inline]
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.base.contains(value)
+    }
+
+    /// Returns a reference to the value in the set, if any, that is equal to
+    /// the given value.
+    #[inline]
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.base.get(value)
+    }
+
+    /// Returns `true` if `self` has no elements in common with `other`.
+    #[inline]
+    pub fn is_disjoint(&self, other: &HashSet<T, S>) -> bool {
+        self.base.is_disjoint(&other.base)
+    }
+
+    /// Returns `true` if the set is a subset of another, i.e., `other`
+    /// contains at least all the values in `self`.
+    #[inline]
+    pub fn is_subset(&self, other: &HashSet<T, S>) -> bool {
+        self.base.is_subset(&other.base)
+    }
+
+    /// Returns `true` if the set is a superset of another, i.e., `self`
+    /// contains at least all the values in `other`.
+    #[inline]
+    pub fn is_superset(&self, other: &HashSet<T, S>) -> bool {
+        self.base.is_superset(&other.base)
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// Returns whether the value was newly inserted.
+    #[inline]
+    pub fn insert(&mut self, value: T) -> bool {
+        self.base.insert(value)
+    }
+
+    /// Adds a value to the set, replacing the existing value, if any, that is
+    /// equal to the given one. Returns the replaced value.
+    #[inline]
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        self.base.replace(value)
+    }
+
+    /// Removes a value from the set. Returns whether the value was present in
+    /// the set.
+    #[inline]
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.base.remove(value)
+    }
+
+    /// Removes and returns the value in the set, if any, that is equal to the
+    /// given one.
+    #[inline]
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.base.take(value)
+    }
+}
+
+impl<T, S> Clone for HashSet<T, S>
+where
+    T: Clone,
+    S: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+        }
+    }
+}
+
+impl<T, S> PartialEq for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &HashSet<T, S>) -> bool {
+        self.base == other.base
+    }
+}
+
+impl<T, S> Eq for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+}
+
+impl<T, S> fmt::Debug for HashSet<T, S>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T, S: Default> Default for HashSet<T, S> {
+    /// Creates an empty `HashSet<T, S>` with the `Default` value for the
+    /// hasher.
+    #[inline]
+    fn default() -> HashSet<T, S> {
+        HashSet::with_hasher(Default::default())
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a HashSet<T, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T, S> IntoIterator for HashSet<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T> {
+        self.base.into_iter()
+    }
+}
+
+impl<T, S> FromIterator<T> for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> HashSet<T, S> {
+        let mut set = HashSet::with_hasher(Default::default());
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, S> Extend<T> for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.base.extend(iter)
+    }
+}
+
+impl<'a, T, S> Extend<&'a T> for HashSet<T, S>
+where
+    T: 'a + Eq + Hash + Copy,
+    S: BuildHasher,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.base.extend(iter)
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for HashSet<T, RandomState>
+where
+    T: Eq + Hash,
+{
+    /// Converts a `[T; N]` into a `HashSet<T>`.
+    fn from(arr: [T; N]) -> Self {
+        HashSet::from_iter(arr)
+    }
+}
@@ -0,0 +1,27 @@
+//! Collection types.
+//!
+//! This module has the same layout as [`std::collections`]: the collections
+//! of [`alloc::collections`] ([`Vec`]-like [`VecDeque`], [`LinkedList`],
+//! [`BTreeMap`], [`BTreeSet`] and [`BinaryHeap`]) are re-exported here,
+//! together with [`HashMap`] and [`HashSet`], which are backed by
+//! [hashbrown](https://crates.io/crates/hashbrown).
+//!
+//! [`std::collections`]: https://doc.rust-lang.org/std/collections/index.html
+//! [`Vec`]: alloc::vec::Vec
+
+#[doc(no_inline)]
+pub use alloc::collections::TryReserveError;
+#[doc(no_inline)]
+pub use alloc::collections::{binary_heap, btree_map, btree_set, linked_list, vec_deque};
+#[doc(no_inline)]
+pub use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
+#[doc(no_inline)]
+pub use core::ops::Bound;
+
+pub mod hash_map;
+pub mod hash_set;
+
+#[doc(inline)]
+pub use self::hash_map::HashMap;
+#[doc(inline)]
+pub use self::hash_set::HashSet;
@@ -3,7 +3,7 @@
 mod stdio;
 
 pub use axio::prelude;
-pub use axio::{BufRead, BufReader, Error, Read, Seek, SeekFrom, Write};
+pub use axio::{BufRead, BufReader, BufWriter, Error, Read, Seek, SeekFrom, Write};
 
 #[doc(hidden)]
 pub use self::stdio::__print_impl;
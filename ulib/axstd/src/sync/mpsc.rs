@@ -0,0 +1,612 @@
+//! Multi-producer, single-consumer FIFO queue communication primitives.
+//!
+//! This module provides message-based communication over channels, similar
+//! to [`std::sync::mpsc`], concretely defined among three types:
+//!
+//! - [`Sender`]
+//! - [`SyncSender`]
+//! - [`Receiver`]
+//!
+//! A [`Sender`] or [`SyncSender`] is used to send data to a [`Receiver`].
+//! Both senders are clone-able (multi-producer) such that many threads can
+//! send simultaneously to one receiver (single-consumer).
+//!
+//! Blocking operations put the current task into a wait queue of the
+//! scheduler, instead of spinning.
+//!
+//! [`std::sync::mpsc`]: https://doc.rust-lang.org/std/sync/mpsc/index.html
+
+extern crate alloc;
+
+use alloc::{collections::VecDeque, sync::Arc};
+use core::fmt;
+use core::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use core::time::Duration;
+
+use arceos_api::task::{self as api, AxWaitQueueHandle};
+
+use super::Mutex;
+
+struct State<T> {
+    queue: VecDeque<T>,
+    /// Total number of messages ever pushed into the queue.
+    sent: u64,
+}
+
+struct Channel<T> {
+    state: Mutex<State<T>>,
+    /// `None` for asynchronous channels.
+    bound: Option<usize>,
+    // The following atomics mirror parts of `state`, since the wait conditions
+    // are evaluated with the run queue locked and cannot take `state`.
+    len: AtomicUsize,
+    received: AtomicU64,
+    senders: AtomicUsize,
+    receiver_alive: AtomicBool,
+    /// Whether the receiver is blocked in `recv`.
+    receiver_waiting: AtomicBool,
+    recv_wq: AxWaitQueueHandle,
+    send_wq: AxWaitQueueHandle,
+}
+
+impl<T> Channel<T> {
+    fn new(bound: Option<usize>) -> Self {
+        Self {
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                sent: 0,
+            }),
+            bound,
+            len: AtomicUsize::new(0),
+            received: AtomicU64::new(0),
+            senders: AtomicUsize::new(1),
+            receiver_alive: AtomicBool::new(true),
+            receiver_waiting: AtomicBool::new(false),
+            recv_wq: AxWaitQueueHandle::new(),
+            send_wq: AxWaitQueueHandle::new(),
+        }
+    }
+
+    fn disconnected(&self) -> bool {
+        self.senders.load(Ordering::Acquire) == 0
+    }
+
+    /// Maximum number of buffered messages. A rendezvous channel (bound 0)
+    /// buffers one message, but its sender waits until it is received.
+    fn capacity(&self) -> usize {
+        self.bound.map_or(usize::MAX, |b| b.max(1))
+    }
+
+    /// Pushes `t` if there is room, returns its sequence number on success.
+    fn try_push(&self, t: T) -> Result<u64, TrySendError<T>> {
+        if !self.receiver_alive.load(Ordering::Acquire) {
+            return Err(TrySendError::Disconnected(t));
+        }
+        let mut state = self.state.lock();
+        if state.queue.len() >= self.capacity() {
+            return Err(TrySendError::Full(t));
+        }
+        state.queue.push_back(t);
+        state.sent += 1;
+        self.len.fetch_add(1, Ordering::Release);
+        let seq = state.sent;
+        drop(state);
+        api::ax_wait_queue_wake(&self.recv_wq, 1);
+        Ok(seq)
+    }
+
+    fn send(&self, mut t: T) -> Result<(), SendError<T>> {
+        let seq = loop {
+            match self.try_push(t) {
+                Ok(seq) => break seq,
+                Err(TrySendError::Disconnected(v)) => return Err(SendError(v)),
+                Err(TrySendError::Full(v)) => {
+                    t = v;
+                    let cap = self.capacity();
+                    api::ax_wait_queue_wait(
+                        &self.send_wq,
+                        || {
+                            self.len.load(Ordering::Acquire) < cap
+                                || !self.receiver_alive.load(Ordering::Acquire)
+                        },
+                        None,
+                    );
+                }
+            }
+        };
+        if self.bound == Some(0) {
+            // Rendezvous: wait until the receiver has taken our message.
+            api::ax_wait_queue_wait(
+                &self.send_wq,
+                || {
+                    self.received.load(Ordering::Acquire) >= seq
+                        || !self.receiver_alive.load(Ordering::Acquire)
+                },
+                None,
+            );
+            if !self.receiver_alive.load(Ordering::Acquire) {
+                let mut state = self.state.lock();
+                // The receiver hung up without taking our message. Since the
+                // queue holds at most one message, it is still the only one
+                // there, so take it back.
+                if self.received.load(Ordering::Acquire) < seq {
+                    if let Some(t) = state.queue.pop_back() {
+                        self.len.fetch_sub(1, Ordering::Release);
+                        return Err(SendError(t));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn try_send(&self, t: T) -> Result<(), TrySendError<T>> {
+        if self.bound == Some(0) && !self.receiver_waiting.load(Ordering::Acquire) {
+            // Rendezvous: only hand the message off to a waiting receiver.
+            return if self.receiver_alive.load(Ordering::Acquire) {
+                Err(TrySendError::Full(t))
+            } else {
+                Err(TrySendError::Disconnected(t))
+            };
+        }
+        self.try_push(t).map(|_| ())
+    }
+
+    fn try_pop(&self) -> Result<T, TryRecvError> {
+        let mut state = self.state.lock();
+        match state.queue.pop_front() {
+            Some(t) => {
+                self.len.fetch_sub(1, Ordering::Release);
+                self.received.fetch_add(1, Ordering::Release);
+                drop(state);
+                if self.bound.is_some() {
+                    api::ax_wait_queue_wake(&self.send_wq, u32::MAX);
+                }
+                Ok(t)
+            }
+            None if self.disconnected() => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    fn recv(&self, timeout: Option<Duration>) -> Result<T, RecvTimeoutError> {
+        loop {
+            match self.try_pop() {
+                Ok(t) => return Ok(t),
+                Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+                Err(TryRecvError::Empty) => {
+                    self.receiver_waiting.store(true, Ordering::Release);
+                    let timed_out = api::ax_wait_queue_wait(
+                        &self.recv_wq,
+                        || self.len.load(Ordering::Acquire) > 0 || self.disconnected(),
+                        timeout,
+                    );
+                    self.receiver_waiting.store(false, Ordering::Release);
+                    if timed_out {
+                        return self.try_pop().map_err(|e| match e {
+                            TryRecvError::Empty => RecvTimeoutError::Timeout,
+                            TryRecvError::Disconnected => RecvTimeoutError::Disconnected,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_sender(&self) {
+        self.senders.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn drop_sender(&self) {
+        if self.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            api::ax_wait_queue_wake(&self.recv_wq, u32::MAX);
+        }
+    }
+
+    fn drop_receiver(&self) {
+        self.receiver_alive.store(false, Ordering::Release);
+        api::ax_wait_queue_wake(&self.send_wq, u32::MAX);
+    }
+}
+
+/// The receiving half of a channel.
+///
+/// Messages sent to the channel can be retrieved using [`recv`]. It is
+/// created by [`channel`] or [`sync_channel`].
+///
+/// [`recv`]: Receiver::recv
+pub struct Receiver<T> {
+    inner: Arc<Channel<T>>,
+}
+
+/// The sending half of an asynchronous channel created by [`channel`].
+///
+/// Messages can be sent through this channel with [`send`], which never
+/// blocks. The `Sender` can be cloned to send to the same channel multiple
+/// times.
+///
+/// [`send`]: Sender::send
+pub struct Sender<T> {
+    inner: Arc<Channel<T>>,
+}
+
+/// The sending half of a synchronous channel created by [`sync_channel`].
+///
+/// Messages can be sent through this channel with [`send`] or
+/// [`try_send`]. [`send`] will block if there is no space in the internal
+/// buffer.
+///
+/// [`send`]: SyncSender::send
+/// [`try_send`]: SyncSender::try_send
+pub struct SyncSender<T> {
+    inner: Arc<Channel<T>>,
+}
+
+unsafe impl<T: Send> Send for Receiver<T> {}
+unsafe impl<T: Send> Send for Sender<T> {}
+unsafe impl<T: Send> Sync for Sender<T> {}
+unsafe impl<T: Send> Send for SyncSender<T> {}
+unsafe impl<T: Send> Sync for SyncSender<T> {}
+
+/// Creates a new asynchronous channel, returning the sender/receiver halves.
+///
+/// All data sent on the [`Sender`] will become available on the [`Receiver`]
+/// in the same order as it was sent, and no [`send`] will block the calling
+/// thread (this channel has an "infinite buffer").
+///
+/// [`send`]: Sender::send
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Channel::new(None));
+    (
+        Sender {
+            inner: inner.clone(),
+        },
+        Receiver { inner },
+    )
+}
+
+/// Creates a new synchronous, bounded channel.
+///
+/// The channel has an internal buffer on which messages will be queued.
+/// `bound` specifies the buffer size. When the internal buffer becomes full,
+/// future sends will block waiting for the buffer to open up. Note that a
+/// buffer size of 0 is valid, in which case this becomes a "rendezvous
+/// channel" where each [`send`] will not return until a [`recv`] is paired
+/// with it.
+///
+/// [`send`]: SyncSender::send
+/// [`recv`]: Receiver::recv
+pub fn sync_channel<T>(bound: usize) -> (SyncSender<T>, Receiver<T>) {
+    let inner = Arc::new(Channel::new(Some(bound)));
+    (
+        SyncSender {
+            inner: inner.clone(),
+        },
+        Receiver { inner },
+    )
+}
+
+impl<T> Sender<T> {
+    /// Attempts to send a value on this channel, returning it back if it
+    /// could not be sent.
+    ///
+    /// A successful send occurs when it is determined that the other end of
+    /// the channel has not hung up already.
+    pub fn send(&self, t: T) -> Result<(), SendError<T>> {
+        self.inner.send(t)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.inner.add_sender();
+        Sender {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.inner.drop_sender();
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+impl<T> SyncSender<T> {
+    /// Sends a value on this synchronous channel.
+    ///
+    /// This function will *block* until space in the internal buffer becomes
+    /// available or a receiver is available to hand off the message to.
+    ///
+    /// An error is returned if the receiver has disconnected. On a rendezvous
+    /// channel this includes the receiver hanging up before taking the
+    /// message, which is then returned in the error.
+    pub fn send(&self, t: T) -> Result<(), SendError<T>> {
+        self.inner.send(t)
+    }
+
+    /// Attempts to send a value on this channel without blocking.
+    ///
+    /// Returns [`TrySendError::Full`] if the buffer is full, or if the
+    /// channel is a rendezvous channel and the receiver is not waiting in
+    /// [`Receiver::recv`] (or holds an unreceived message already).
+    pub fn try_send(&self, t: T) -> Result<(), TrySendError<T>> {
+        self.inner.try_send(t)
+    }
+}
+
+impl<T> Clone for SyncSender<T> {
+    fn clone(&self) -> SyncSender<T> {
+        self.inner.add_sender();
+        SyncSender {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for SyncSender<T> {
+    fn drop(&mut self) {
+        self.inner.drop_sender();
+    }
+}
+
+impl<T> fmt::Debug for SyncSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncSender").finish_non_exhaustive()
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Attempts to return a pending value on this receiver without blocking.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.inner.try_pop()
+    }
+
+    /// Attempts to wait for a value on this receiver, returning an error if
+    /// the corresponding channel has hung up.
+    ///
+    /// This function will always block the current thread if there is no data
+    /// available and it's possible for more data to be sent.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.inner.recv(None).map_err(|_| RecvError)
+    }
+
+    /// Attempts to wait for a value on this receiver, returning an error if
+    /// the corresponding channel has hung up, or if it waits more than
+    /// `timeout`.
+    ///
+    /// The timeout is ignored if the `irq` feature is not enabled.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        self.inner.recv(Some(timeout))
+    }
+
+    /// Returns an iterator that will block waiting for messages, but never
+    /// [`panic!`]. It will return [`None`] when the channel has hung up.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { rx: self }
+    }
+
+    /// Returns an iterator that will attempt to yield all pending values
+    /// without blocking.
+    pub fn try_iter(&self) -> TryIter<'_, T> {
+        TryIter { rx: self }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.inner.drop_receiver();
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+/// An iterator over messages on a [`Receiver`], created by [`iter`].
+///
+/// [`iter`]: Receiver::iter
+#[derive(Debug)]
+pub struct Iter<'a, T: 'a> {
+    rx: &'a Receiver<T>,
+}
+
+/// An iterator that attempts to yield all pending values for a [`Receiver`],
+/// created by [`try_iter`].
+///
+/// [`try_iter`]: Receiver::try_iter
+#[derive(Debug)]
+pub struct TryIter<'a, T: 'a> {
+    rx: &'a Receiver<T>,
+}
+
+/// An owning iterator over messages on a [`Receiver`], created by
+/// [`into_iter`](IntoIterator::into_iter).
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    rx: Receiver<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+}
+
+impl<'a, T> Iterator for TryIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.try_recv().ok()
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Receiver<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { rx: self }
+    }
+}
+
+/// An error returned from the [`Sender::send`] or [`SyncSender::send`]
+/// function on **channel**s.
+///
+/// A **send** operation can only fail if the receiving end of a channel is
+/// disconnected. The error contains the data being sent as a payload so it
+/// can be recovered.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct SendError<T>(pub T);
+
+/// An error returned from the [`recv`] function on a [`Receiver`].
+///
+/// The [`recv`] operation can only fail if the sending half of a channel is
+/// disconnected, implying that no further messages will ever be received.
+///
+/// [`recv`]: Receiver::recv
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct RecvError;
+
+/// This enumeration is the list of the possible reasons that
+/// [`try_recv`](Receiver::try_recv) could not return data when called.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TryRecvError {
+    /// This channel is currently empty, but the sender(s) have not yet
+    /// disconnected, so data may yet become available.
+    Empty,
+    /// The channel's sending half has become disconnected, and there will
+    /// never be any more data received on it.
+    Disconnected,
+}
+
+/// This enumeration is the list of possible errors that made
+/// [`recv_timeout`](Receiver::recv_timeout) unable to return data when
+/// called.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum RecvTimeoutError {
+    /// This channel is currently empty, but the sender(s) have not yet
+    /// disconnected, so data may yet become available.
+    Timeout,
+    /// The channel's sending half has become disconnected, and there will
+    /// never be any more data received on it.
+    Disconnected,
+}
+
+/// This enumeration is the list of the possible error outcomes for the
+/// [`try_send`](SyncSender::try_send) method.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum TrySendError<T> {
+    /// The data could not be sent on the [`sync_channel`] because it would
+    /// require that the callee block to send the data.
+    Full(T),
+    /// This [`sync_channel`]'s receiving half has disconnected, so the data
+    /// could not be sent. The data is returned back to the callee in this
+    /// case.
+    Disconnected(T),
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendError").finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "sending on a closed channel".fmt(f)
+    }
+}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TrySendError::Full(..) => "Full(..)".fmt(f),
+            TrySendError::Disconnected(..) => "Disconnected(..)".fmt(f),
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TrySendError::Full(..) => "sending on a full channel".fmt(f),
+            TrySendError::Disconnected(..) => "sending on a closed channel".fmt(f),
+        }
+    }
+}
+
+impl<T> From<SendError<T>> for TrySendError<T> {
+    fn from(err: SendError<T>) -> TrySendError<T> {
+        match err {
+            SendError(t) => TrySendError::Disconnected(t),
+        }
+    }
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "receiving on a closed channel".fmt(f)
+    }
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TryRecvError::Empty => "receiving on an empty channel".fmt(f),
+            TryRecvError::Disconnected => "receiving on a closed channel".fmt(f),
+        }
+    }
+}
+
+impl From<RecvError> for TryRecvError {
+    fn from(err: RecvError) -> TryRecvError {
+        match err {
+            RecvError => TryRecvError::Disconnected,
+        }
+    }
+}
+
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            RecvTimeoutError::Timeout => "timed out waiting on channel".fmt(f),
+            RecvTimeoutError::Disconnected => "channel is empty and sending half is closed".fmt(f),
+        }
+    }
+}
+
+impl From<RecvError> for RecvTimeoutError {
+    fn from(err: RecvError) -> RecvTimeoutError {
+        match err {
+            RecvError => RecvTimeoutError::Disconnected,
+        }
+    }
+}
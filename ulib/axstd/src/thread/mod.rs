@@ -24,6 +24,16 @@ pub fn exit(exit_code: i32) -> ! {
     api::ax_exit(exit_code);
 }
 
+/// Determines whether the current thread is unwinding because of panic.
+///
+/// ArceOS does not support unwinding: a panic in any thread terminates the
+/// whole system immediately, without running destructors. Therefore no code
+/// can ever observe a panicking thread, and this function always returns
+/// `false`.
+pub fn panicking() -> bool {
+    false
+}
+
 /// Current thread is going to sleep for the given duration.
 ///
 /// If one of `multitask` or `irq` features is not enabled, it uses busy-wait
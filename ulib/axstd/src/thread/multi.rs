@@ -16,6 +16,7 @@ pub struct ThreadId(NonZeroU64);
 /// A handle to a thread.
 pub struct Thread {
     id: ThreadId,
+    name: Option<String>,
 }
 
 impl ThreadId {
@@ -27,9 +28,10 @@ impl ThreadId {
 }
 
 impl Thread {
-    fn from_id(id: u64) -> Self {
+    fn new(id: u64, name: String) -> Self {
         Self {
             id: ThreadId(NonZeroU64::new(id).unwrap()),
+            name: if name.is_empty() { None } else { Some(name) },
         }
     }
 
@@ -37,6 +39,14 @@ impl Thread {
     pub fn id(&self) -> ThreadId {
         self.id
     }
+
+    /// Gets the thread's name.
+    ///
+    /// Returns [`None`] if the thread is unnamed, i.e., it was spawned without
+    /// [`Builder::name`].
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
 }
 
 /// Thread factory, which can be used in order to configure the properties of
@@ -115,9 +125,9 @@ impl Builder {
             drop(their_packet);
         };
 
-        let task = api::ax_spawn(main, name, stack_size);
+        let task = api::ax_spawn(main, name.clone(), stack_size);
         Ok(JoinHandle {
-            thread: Thread::from_id(task.id()),
+            thread: Thread::new(task.id(), name),
             native: task,
             packet: my_packet,
         })
@@ -127,7 +137,7 @@ impl Builder {
 /// Gets a handle to the thread that invokes it.
 pub fn current() -> Thread {
     let id = api::ax_current_task_id();
-    Thread::from_id(id)
+    Thread::new(id, api::ax_current_task_name())
 }
 
 /// Spawns a new thread, returning a [`JoinHandle`] for it.
@@ -177,13 +187,37 @@ impl<T> JoinHandle<T> {
     ///
     /// This function will return immediately if the associated thread has
     /// already finished.
-    pub fn join(mut self) -> io::Result<T> {
-        api::ax_wait_for_exit(self.native).ok_or_else(|| ax_err_type!(BadState))?;
-        Arc::get_mut(&mut self.packet)
-            .unwrap()
-            .result
-            .get_mut()
-            .take()
+    ///
+    /// On success, the value returned by the thread's closure is returned.
+    /// An error of kind [`BadState`](io::Error::BadState) is returned if the
+    /// thread was terminated by [`exit`](super::exit) before its closure
+    /// returned; use [`join_with_exit_code`] to get the exit code in that
+    /// case. Unlike the standard library, there is no panic payload to
+    /// report: a panic in any thread terminates the whole system (see
+    /// [`panicking`](super::panicking)).
+    ///
+    /// [`join_with_exit_code`]: JoinHandle::join_with_exit_code
+    pub fn join(self) -> io::Result<T> {
+        self.join_with_exit_code()
+            .0
             .ok_or_else(|| ax_err_type!(BadState))
     }
+
+    /// Waits for the associated thread to finish, and returns the value
+    /// returned by its closure together with its exit code.
+    ///
+    /// The value is [`None`] if the thread was terminated by
+    /// [`exit`](super::exit) before its closure returned, and the exit code
+    /// is then the one passed to [`exit`](super::exit). A thread whose closure
+    /// returns normally has exit code 0.
+    pub fn join_with_exit_code(self) -> (Option<T>, i32) {
+        // `ax_wait_for_exit` returns only after the task has exited, and
+        // always reports its exit code then.
+        let exit_code = api::ax_wait_for_exit(self.native).unwrap_or_default();
+        // SAFETY: the thread has exited and will never touch the packet again.
+        // If it called `exit`, its reference to the packet was never dropped,
+        // so `Arc::get_mut` cannot be used here.
+        let ret = unsafe { (*self.packet.result.get()).take() };
+        (ret, exit_code)
+    }
 }